@@ -1,15 +1,25 @@
 #[cfg(test)]
 mod tests;
 mod utils;
+pub mod writer;
 
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
 
+use flate2::read::ZlibDecoder;
+
 // Constants mapping original C-constant values
 const EIDENTSIZE: usize = 16;
 const SHN_UNDEF: u16 = 0;
 
+// e_ident[EI_CLASS] values
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+// e_ident[EI_DATA] values
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+
 // Section Header types
 const SHT_NULL: u32 = 0;
 const SHT_PROGBITS: u32 = 1;
@@ -21,6 +31,7 @@ const SHT_DYN: u32 = 6;
 const SHT_NOTE: u32 = 7;
 const SHT_NOBITS: u32 = 8;
 const SHT_REL: u32 = 9;
+const SHT_DYNSYM: u32 = 11;
 
 // Constants for various file types, machine types, etc
 const TYPE_NONE: u16 = 0;
@@ -29,6 +40,49 @@ const TYPE_EXEC: u16 = 2;
 const TYPE_DYN: u16 = 3;
 const TYPE_CORE: u16 = 4;
 
+// Program Header types
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PT_INTERP: u32 = 3;
+const PT_NOTE: u32 = 4;
+const PT_PHDR: u32 = 6;
+const PT_GNU_STACK: u32 = 0x6474e551;
+
+// Symbol binding (st_info >> 4)
+const STB_LOCAL: u8 = 0;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+
+// Symbol type (st_info & 0xf)
+const STT_NOTYPE: u8 = 0;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STT_SECTION: u8 = 3;
+const STT_FILE: u8 = 4;
+
+// Dynamic section tags (d_tag)
+const DT_NULL: i64 = 0;
+const DT_NEEDED: i64 = 1;
+const DT_HASH: i64 = 4;
+const DT_STRTAB: i64 = 5;
+const DT_SYMTAB: i64 = 6;
+const DT_STRSZ: i64 = 10;
+const DT_SYMENT: i64 = 11;
+const DT_SONAME: i64 = 14;
+const DT_REL: i64 = 17;
+const DT_RELSZ: i64 = 18;
+const DT_RELENT: i64 = 19;
+
+// sh_flags bit marking a section as compressed (Elf64_Chdr/Elf32_Chdr prefixed)
+const SHF_COMPRESSED: u64 = 0x800;
+
+// Chdr ch_type values
+const ELFCOMPRESS_ZLIB: u32 = 1;
+const ELFCOMPRESS_ZSTD: u32 = 2;
+
+// GNU build-id note type, under name "GNU"
+const NT_GNU_BUILD_ID: u32 = 3;
+
 #[derive(Debug)]
 pub struct ElfError {
     desc: String,
@@ -62,9 +116,25 @@ impl std::fmt::Display for ElfError {
     }
 }
 
+// Whether a file is 32-bit or 64-bit, taken from e_ident[EI_CLASS]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfClass {
+    Elf32,
+    Elf64,
+}
+
+// Byte order of multi-byte fields, taken from e_ident[EI_DATA]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
 // ElfHeaders represent the ELF headers of a given file
 pub struct ElfHeaders {
     pub ident: [char; EIDENTSIZE],
+    pub class: ElfClass,
+    pub endianness: Endianness,
     pub file_type: u16,
     pub machine: u16,
     pub version: u32,
@@ -118,6 +188,8 @@ impl ElfHeaders {
     pub fn new() -> Self {
         ElfHeaders {
             ident: [0 as char; EIDENTSIZE],
+            class: ElfClass::Elf64,
+            endianness: Endianness::Little,
             file_type: 0,
             machine: 0,
             version: 0,
@@ -148,7 +220,7 @@ impl ElfHeaders {
     // Read in headers from a binary ELF file by name
     pub fn from_file(&mut self, f: &mut File) -> Result<(), ElfError> {
         let mut buffer = [0; EIDENTSIZE];
-        f.read(&mut buffer)?;
+        f.read_exact(&mut buffer)?;
         // Check to ensure the file's magic bits are set to ELF spec
         if buffer[0..4] != [0x7f, 0x45, 0x4c, 0x46] {
             return Err(ElfError {
@@ -159,40 +231,55 @@ impl ElfHeaders {
         for (i, each) in buffer.into_iter().enumerate() {
             self.ident[i] = *each as char;
         }
+        // e_ident[4] and [5] tell us how to interpret everything that follows
+        self.class = match buffer[4] {
+            ELFCLASS32 => ElfClass::Elf32,
+            ELFCLASS64 => ElfClass::Elf64,
+            _ => {
+                return Err(ElfError {
+                    desc: String::from("Unsupported or invalid ELF class"),
+                    cause: None,
+                })
+            }
+        };
+        self.endianness = match buffer[5] {
+            ELFDATA2LSB => Endianness::Little,
+            ELFDATA2MSB => Endianness::Big,
+            _ => {
+                return Err(ElfError {
+                    desc: String::from("Unsupported or invalid ELF data encoding"),
+                    cause: None,
+                })
+            }
+        };
         // Read in the next several u16s
         let mut buffer = [0; 2];
-        f.read(&mut buffer)?;
-        self.file_type = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.machine = utils::bytes_to_u16(buffer);
+        f.read_exact(&mut buffer)?;
+        self.file_type = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.machine = utils::bytes_to_u16(buffer, self.endianness);
         let mut buffer = [0; 4];
-        f.read(&mut buffer)?;
-        self.version = utils::bytes_to_u32(buffer);
-        // 64 bit ELF uses 64 bit address size. 32 uses 32. Need to update this to support both
-        // TODO will probably need an initial scan of magic bits to determine arch
-        // then an appropriate struct
-        let mut buffer64 = [0; 8];
-        f.read(&mut buffer64)?;
-        self.entry_addr = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer64)?;
-        self.program_offset = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer64)?;
-        self.section_offset = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer)?;
-        self.cpu_flags = utils::bytes_to_u32(buffer);
+        f.read_exact(&mut buffer)?;
+        self.version = utils::bytes_to_u32(buffer, self.endianness);
+        // entry_addr/program_offset/section_offset are 4 bytes under ELFCLASS32, 8 under ELFCLASS64
+        self.entry_addr = utils::read_addr(f, self.class, self.endianness)?;
+        self.program_offset = utils::read_addr(f, self.class, self.endianness)?;
+        self.section_offset = utils::read_addr(f, self.class, self.endianness)?;
+        f.read_exact(&mut buffer)?;
+        self.cpu_flags = utils::bytes_to_u32(buffer, self.endianness);
         let mut buffer = [0; 2];
-        f.read(&mut buffer)?;
-        self.ehead_size = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.pheader_size = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.pheader_count = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.sheader_size = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.sheader_count = utils::bytes_to_u16(buffer);
-        f.read(&mut buffer)?;
-        self.str_header_index = utils::bytes_to_u16(buffer);
+        f.read_exact(&mut buffer)?;
+        self.ehead_size = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.pheader_size = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.pheader_count = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.sheader_size = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.sheader_count = utils::bytes_to_u16(buffer, self.endianness);
+        f.read_exact(&mut buffer)?;
+        self.str_header_index = utils::bytes_to_u16(buffer, self.endianness);
         Ok(())
     }
 
@@ -202,7 +289,7 @@ impl ElfHeaders {
         for i in 0..self.sheader_count {
             let mut section_header =
                 SectionHeader::new(self.section_offset + (self.sheader_size * i) as u64);
-            section_header.from_file(f)?;
+            section_header.from_file(f, self.class, self.endianness)?;
             headers.push(section_header);
         }
         let mut sections = Vec::with_capacity(headers.len());
@@ -248,6 +335,18 @@ impl ElfHeaders {
         }
         Ok(sections)
     }
+
+    // Read the program header table (the segments the loader maps at runtime)
+    pub fn programs_from_file(&self, f: &mut File) -> Result<Vec<ProgramHeader>, ElfError> {
+        let mut headers = Vec::with_capacity(self.pheader_count as usize);
+        for i in 0..self.pheader_count {
+            let mut header =
+                ProgramHeader::new(self.program_offset + self.pheader_size as u64 * i as u64);
+            header.from_file(f, self.class, self.endianness)?;
+            headers.push(header);
+        }
+        Ok(headers)
+    }
 }
 
 pub struct Section {
@@ -261,23 +360,412 @@ impl std::fmt::Display for Section {
     }
 }
 
+impl Section {
+    // Decode a SymbolTable/DynSym section's entries, resolving each symbol's name through
+    // the string table section pointed to by this section's sh_link
+    pub fn symbols_from_section(&self, sections: &[Section]) -> Result<Vec<Symbol>, ElfError> {
+        if self.header.sec_type != SectionType::SymbolTable {
+            return Err(ElfError {
+                desc: String::from("Section is not a symbol table"),
+                cause: None,
+            });
+        }
+        let str_tbl = sections.get(self.header.link as usize).ok_or_else(|| ElfError {
+            desc: String::from("Symbol table's sh_link does not point at a valid section"),
+            cause: None,
+        })?;
+        let entry_size = self.header.entry_size as usize;
+        let expected_size = match self.header.class {
+            ElfClass::Elf32 => 16,
+            ElfClass::Elf64 => 24,
+        };
+        if entry_size != expected_size {
+            return Err(ElfError {
+                desc: format!(
+                    "Symbol table has an unexpected sh_entsize ({}), expected {}",
+                    entry_size, expected_size
+                ),
+                cause: None,
+            });
+        }
+        let mut symbols = Vec::with_capacity(self.data.len() / entry_size);
+        for entry in self.data.chunks(entry_size) {
+            // A truncated symbol table can leave a short final chunk; stop rather than index out of bounds
+            if entry.len() < entry_size {
+                break;
+            }
+            let (name, info, shndx, value, size) = match self.header.class {
+                ElfClass::Elf32 => (
+                    utils::slice_to_u32(&entry[0..4], self.header.endianness),
+                    entry[12],
+                    utils::slice_to_u16(&entry[14..16], self.header.endianness),
+                    utils::slice_to_u32(&entry[4..8], self.header.endianness) as u64,
+                    utils::slice_to_u32(&entry[8..12], self.header.endianness) as u64,
+                ),
+                ElfClass::Elf64 => (
+                    utils::slice_to_u32(&entry[0..4], self.header.endianness),
+                    entry[4],
+                    utils::slice_to_u16(&entry[6..8], self.header.endianness),
+                    utils::slice_to_u64(&entry[8..16], self.header.endianness),
+                    utils::slice_to_u64(&entry[16..24], self.header.endianness),
+                ),
+            };
+            symbols.push(Symbol {
+                name: utils::read_null_term_str(name, &str_tbl.data),
+                binding: match info >> 4 {
+                    STB_LOCAL => SymbolBinding::Local,
+                    STB_GLOBAL => SymbolBinding::Global,
+                    STB_WEAK => SymbolBinding::Weak,
+                    _ => SymbolBinding::Unknown,
+                },
+                sym_type: match info & 0xf {
+                    STT_NOTYPE => SymbolType::NoType,
+                    STT_OBJECT => SymbolType::Object,
+                    STT_FUNC => SymbolType::Func,
+                    STT_SECTION => SymbolType::Section,
+                    STT_FILE => SymbolType::File,
+                    _ => SymbolType::Unknown,
+                },
+                shndx,
+                value,
+                size,
+            });
+        }
+        Ok(symbols)
+    }
+
+    // Decode a Rel/Rela section's entries, resolving the symbol each one refers to. `file_type`
+    // (ElfHeaders::file_type) is needed because r_offset means different things depending on it:
+    // for ET_REL it's section-relative, so sh_info's target section lets us compute a file
+    // offset; for ET_EXEC/ET_DYN it's already an absolute virtual address (and sh_info is often
+    // 0, the NULL section), so no such computation is possible here.
+    pub fn relocations_from_section(&self, sections: &[Section], file_type: u16) -> Result<Vec<Relocation>, ElfError> {
+        let has_addend = match self.header.sec_type {
+            SectionType::Rela => true,
+            SectionType::Rel => false,
+            _ => {
+                return Err(ElfError {
+                    desc: String::from("Section is not a relocation section"),
+                    cause: None,
+                })
+            }
+        };
+        let symtab = sections.get(self.header.link as usize).ok_or_else(|| ElfError {
+            desc: String::from("Relocation section's sh_link does not point at a valid section"),
+            cause: None,
+        })?;
+        let symbols = symtab.symbols_from_section(sections)?;
+        // sh_info only identifies a patch target section for ET_REL; for ET_EXEC/ET_DYN it's
+        // conventionally 0 (the NULL section) since r_offset is already an absolute vaddr
+        let target = if file_type == TYPE_RELO {
+            Some(sections.get(self.header.info as usize).ok_or_else(|| ElfError {
+                desc: String::from("Relocation section's sh_info does not point at a valid section"),
+                cause: None,
+            })?)
+        } else {
+            None
+        };
+        let entry_size = if has_addend { 24 } else { 16 };
+        let mut relocations = Vec::with_capacity(self.data.len() / entry_size);
+        for entry in self.data.chunks(entry_size) {
+            // A truncated relocation section can leave a short final chunk; stop rather than
+            // index out of bounds
+            if entry.len() < entry_size {
+                break;
+            }
+            let offset = utils::slice_to_u64(&entry[0..8], self.header.endianness);
+            let info = utils::slice_to_u64(&entry[8..16], self.header.endianness);
+            let addend = if has_addend {
+                utils::slice_to_i64(&entry[16..24], self.header.endianness)
+            } else {
+                0
+            };
+            let sym_name = symbols
+                .get((info >> 32) as usize)
+                .map(|s| s.name.clone())
+                .unwrap_or_default();
+            relocations.push(Relocation {
+                offset,
+                sym_name,
+                reloc_type: (info & 0xffffffff) as u32,
+                addend,
+                target_offset: target.map(|t| t.header.offset + offset),
+            });
+        }
+        Ok(relocations)
+    }
+
+    // Decode the .dynamic section, resolving DT_NEEDED/DT_SONAME entries into library names
+    // via the dynamic string table pointed to by this section's sh_link
+    pub fn dynamic_from_section(&self, sections: &[Section]) -> Result<Vec<DynEntry>, ElfError> {
+        if self.header.sec_type != SectionType::Dynamic {
+            return Err(ElfError {
+                desc: String::from("Section is not a dynamic linking table"),
+                cause: None,
+            });
+        }
+        let str_tbl = sections.get(self.header.link as usize).ok_or_else(|| ElfError {
+            desc: String::from("Dynamic section's sh_link does not point at a valid section"),
+            cause: None,
+        })?;
+        let entry_size = match self.header.class {
+            ElfClass::Elf32 => 8,
+            ElfClass::Elf64 => 16,
+        };
+        let mut entries = vec![];
+        for chunk in self.data.chunks(entry_size) {
+            // A truncated dynamic section can leave a short final chunk; stop rather than
+            // index out of bounds
+            if chunk.len() < entry_size {
+                break;
+            }
+            let (tag, value) = match self.header.class {
+                ElfClass::Elf32 => (
+                    utils::slice_to_u32(&chunk[0..4], self.header.endianness) as i64,
+                    utils::slice_to_u32(&chunk[4..8], self.header.endianness) as u64,
+                ),
+                ElfClass::Elf64 => (
+                    utils::slice_to_i64(&chunk[0..8], self.header.endianness),
+                    utils::slice_to_u64(&chunk[8..16], self.header.endianness),
+                ),
+            };
+            let kind = match tag {
+                DT_NULL => DynTag::Null,
+                DT_NEEDED => DynTag::Needed,
+                DT_HASH => DynTag::Hash,
+                DT_STRTAB => DynTag::StrTab,
+                DT_SYMTAB => DynTag::SymTab,
+                DT_STRSZ => DynTag::StrSz,
+                DT_SYMENT => DynTag::SymEnt,
+                DT_SONAME => DynTag::SoName,
+                DT_REL => DynTag::Rel,
+                DT_RELSZ => DynTag::RelSz,
+                DT_RELENT => DynTag::RelEnt,
+                _ => DynTag::Unknown,
+            };
+            // DT_NEEDED/DT_SONAME store an offset into the dynamic string table rather than a value
+            let name = match kind {
+                DynTag::Needed | DynTag::SoName => {
+                    Some(utils::read_null_term_str(value as u32, &str_tbl.data))
+                }
+                _ => None,
+            };
+            let is_null = kind == DynTag::Null;
+            entries.push(DynEntry { tag, kind, value, name });
+            if is_null {
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    // Inflate a SHF_COMPRESSED section's payload; sections without the flag are returned
+    // as a clone of `data`, leaving the raw field untouched either way
+    pub fn decompressed_data(&self) -> Result<Vec<u8>, ElfError> {
+        if self.header.flags & SHF_COMPRESSED == 0 {
+            return Ok(self.data.clone());
+        }
+        let endianness = self.header.endianness;
+        // Elf32_Chdr has no ch_reserved padding field, so the payload starts 12 bytes earlier
+        let hdr_len = match self.header.class {
+            ElfClass::Elf32 => 12,
+            ElfClass::Elf64 => 24,
+        };
+        if self.data.len() < hdr_len {
+            return Err(ElfError {
+                desc: String::from("Compressed section data is shorter than its Chdr header"),
+                cause: None,
+            });
+        }
+        let (ch_type, ch_size) = match self.header.class {
+            ElfClass::Elf32 => (
+                utils::slice_to_u32(&self.data[0..4], endianness),
+                utils::slice_to_u32(&self.data[4..8], endianness) as u64,
+            ),
+            ElfClass::Elf64 => (
+                utils::slice_to_u32(&self.data[0..4], endianness),
+                utils::slice_to_u64(&self.data[8..16], endianness),
+            ),
+        };
+        let payload = &self.data[hdr_len..];
+        // ch_size is the claimed uncompressed size from file bytes; treat it only as a capacity
+        // hint, not a hard allocation, so a bogus/huge value can't force an outsized allocation
+        const MAX_CAPACITY_HINT: u64 = 1 << 30;
+        let mut out = Vec::with_capacity(std::cmp::min(ch_size, MAX_CAPACITY_HINT) as usize);
+        match ch_type {
+            ELFCOMPRESS_ZLIB => {
+                ZlibDecoder::new(payload).read_to_end(&mut out)?;
+            }
+            ELFCOMPRESS_ZSTD => {
+                out = zstd::stream::decode_all(payload).map_err(|e| ElfError {
+                    desc: String::from("Failed to decompress zstd section"),
+                    cause: Some(e),
+                })?;
+            }
+            _ => {
+                return Err(ElfError {
+                    desc: format!("Unsupported compression type {}", ch_type),
+                    cause: None,
+                })
+            }
+        }
+        Ok(out)
+    }
+
+    // Decode a Notes section's padded name/desc entries (also the layout of PT_NOTE segments)
+    pub fn notes_from_section(&self) -> Result<Vec<Note>, ElfError> {
+        if self.header.sec_type != SectionType::Notes {
+            return Err(ElfError {
+                desc: String::from("Section is not a notes section"),
+                cause: None,
+            });
+        }
+        let endianness = self.header.endianness;
+        let data = &self.data;
+        let mut notes = vec![];
+        let mut pos = 0;
+        while pos + 12 <= data.len() {
+            let namesz = utils::slice_to_u32(&data[pos..pos + 4], endianness) as usize;
+            let descsz = utils::slice_to_u32(&data[pos + 4..pos + 8], endianness) as usize;
+            let note_type = utils::slice_to_u32(&data[pos + 8..pos + 12], endianness);
+            pos += 12;
+
+            let name_end = pos + namesz;
+            if name_end > data.len() {
+                break;
+            }
+            let name = data[pos..name_end]
+                .iter()
+                .take_while(|&&b| b != 0)
+                .map(|&b| b as char)
+                .collect();
+            pos = name_end + pad4(namesz);
+
+            let desc_end = pos + descsz;
+            if desc_end > data.len() {
+                break;
+            }
+            let desc = data[pos..desc_end].to_vec();
+            pos = desc_end + pad4(descsz);
+
+            notes.push(Note {
+                name,
+                note_type,
+                desc,
+            });
+        }
+        Ok(notes)
+    }
+}
+
+// Notes pad both the name and descriptor to a 4-byte boundary
+fn pad4(len: usize) -> usize {
+    (4 - (len % 4)) % 4
+}
+
+pub struct Note {
+    pub name: String,
+    pub note_type: u32,
+    pub desc: Vec<u8>,
+}
+
+impl Note {
+    pub fn is_gnu_build_id(&self) -> bool {
+        self.name == "GNU" && self.note_type == NT_GNU_BUILD_ID
+    }
+
+    // Render the GNU build-id descriptor as lowercase hex, e.g. for symbol-server lookups
+    pub fn build_id_hex(&self) -> Option<String> {
+        if !self.is_gnu_build_id() {
+            return None;
+        }
+        Some(self.desc.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+}
+
+pub struct DynEntry {
+    pub tag: i64,
+    pub kind: DynTag,
+    pub value: u64,
+    // Resolved library/soname for DT_NEEDED and DT_SONAME entries
+    pub name: Option<String>,
+}
+
+#[derive(PartialEq)]
+pub enum DynTag {
+    Null,
+    Needed,
+    Hash,
+    StrTab,
+    SymTab,
+    StrSz,
+    SymEnt,
+    SoName,
+    Rel,
+    RelSz,
+    RelEnt,
+    Unknown,
+}
+
+pub struct Relocation {
+    pub offset: u64,
+    pub sym_name: String,
+    // Machine-specific; meaning depends on e_machine
+    pub reloc_type: u32,
+    pub addend: i64,
+    // Only meaningful for ET_REL objects, where r_offset is section-relative and can be added
+    // to the target section's sh_offset to get a file offset. For ET_EXEC/ET_DYN, r_offset is
+    // already an absolute virtual address, so callers must do their own vaddr-to-file-offset
+    // translation (e.g. via the program headers) instead.
+    pub target_offset: Option<u64>,
+}
+
+pub struct Symbol {
+    pub name: String,
+    pub binding: SymbolBinding,
+    pub sym_type: SymbolType,
+    pub shndx: u16,
+    pub value: u64,
+    pub size: u64,
+}
+
+#[derive(PartialEq)]
+pub enum SymbolBinding {
+    Local,
+    Global,
+    Weak,
+    Unknown,
+}
+
+#[derive(PartialEq)]
+pub enum SymbolType {
+    NoType,
+    Object,
+    Func,
+    Section,
+    File,
+    Unknown,
+}
+
 pub struct SectionHeader {
     ptr: u64,
+    class: ElfClass,
+    endianness: Endianness,
     pub str_name: String,
     name: u32,
-    i_type: u32,
+    pub i_type: u32,
     pub sec_type: SectionType,
     pub flags: u64,
     pub img_addr: u64,
     offset: u64,
     size: u64,
-    link: u32,
+    pub link: u32,
     pub info: u32,
     align: u64,
     pub entry_size: u64,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum SectionType {
     Unused,
     ProgramData,
@@ -296,6 +784,8 @@ impl SectionHeader {
     pub fn new(location: u64) -> Self {
         SectionHeader {
             ptr: location,
+            class: ElfClass::Elf64,
+            endianness: Endianness::Little,
             str_name: String::from(""),
             sec_type: SectionType::Unknown,
             name: 0,
@@ -311,18 +801,24 @@ impl SectionHeader {
         }
     }
 
-    pub fn from_file(&mut self, f: &mut File) -> Result<(), ElfError> {
+    pub fn from_file(
+        &mut self,
+        f: &mut File,
+        class: ElfClass,
+        endianness: Endianness,
+    ) -> Result<(), ElfError> {
         f.seek(io::SeekFrom::Start(self.ptr))?;
         let mut buffer = [0; 4];
-        f.read(&mut buffer)?;
-        self.name = utils::bytes_to_u32(buffer);
-        f.read(&mut buffer)?;
-        self.i_type = utils::bytes_to_u32(buffer);
+        f.read_exact(&mut buffer)?;
+        self.name = utils::bytes_to_u32(buffer, endianness);
+        f.read_exact(&mut buffer)?;
+        self.i_type = utils::bytes_to_u32(buffer, endianness);
         // set type from raw integer type
         self.sec_type = match self.i_type {
             SHT_NULL => SectionType::Unused,
             SHT_PROGBITS => SectionType::ProgramData,
             SHT_SYMTAB => SectionType::SymbolTable,
+            SHT_DYNSYM => SectionType::SymbolTable,
             SHT_STRTAB => SectionType::StringTable,
             SHT_RELA => SectionType::Rela,
             SHT_HASH => SectionType::Hash,
@@ -332,15 +828,20 @@ impl SectionHeader {
             SHT_REL => SectionType::Rel,
             _ => SectionType::Unknown
         };
-        let mut buffer64 = [0; 8];
-        f.read(&mut buffer64)?;
-        self.flags = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer64)?;
-        self.img_addr = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer64)?;
-        self.offset = utils::bytes_to_u64(buffer64);
-        f.read(&mut buffer64)?;
-        self.size = utils::bytes_to_u64(buffer64);
+        // flags/addr/offset/size are 4 bytes under ELFCLASS32, 8 under ELFCLASS64
+        self.flags = utils::read_addr(f, class, endianness)?;
+        self.img_addr = utils::read_addr(f, class, endianness)?;
+        self.offset = utils::read_addr(f, class, endianness)?;
+        self.size = utils::read_addr(f, class, endianness)?;
+        // sh_link/sh_info are always 4 bytes; sh_addralign/sh_entsize follow the class split
+        f.read_exact(&mut buffer)?;
+        self.link = utils::bytes_to_u32(buffer, endianness);
+        f.read_exact(&mut buffer)?;
+        self.info = utils::bytes_to_u32(buffer, endianness);
+        self.align = utils::read_addr(f, class, endianness)?;
+        self.entry_size = utils::read_addr(f, class, endianness)?;
+        self.class = class;
+        self.endianness = endianness;
         Ok(())
     }
 
@@ -392,3 +893,133 @@ address: {:<22x} offset: {:<18x} size: {:x}",
         )
     }
 }
+
+pub struct ProgramHeader {
+    ptr: u64,
+    i_type: u32,
+    pub p_type: ProgramHeaderType,
+    pub flags: u32,
+    pub offset: u64,
+    pub vaddr: u64,
+    pub paddr: u64,
+    pub filesz: u64,
+    pub memsz: u64,
+    pub align: u64,
+}
+
+#[derive(PartialEq)]
+pub enum ProgramHeaderType {
+    Load,
+    Dynamic,
+    Interp,
+    Note,
+    Phdr,
+    GnuStack,
+    Unknown,
+}
+
+impl ProgramHeader {
+    pub fn new(location: u64) -> Self {
+        ProgramHeader {
+            ptr: location,
+            i_type: 0,
+            p_type: ProgramHeaderType::Unknown,
+            flags: 0,
+            offset: 0,
+            vaddr: 0,
+            paddr: 0,
+            filesz: 0,
+            memsz: 0,
+            align: 0,
+        }
+    }
+
+    pub fn from_file(
+        &mut self,
+        f: &mut File,
+        class: ElfClass,
+        endianness: Endianness,
+    ) -> Result<(), ElfError> {
+        f.seek(io::SeekFrom::Start(self.ptr))?;
+        let mut buffer = [0; 4];
+        f.read_exact(&mut buffer)?;
+        self.i_type = utils::bytes_to_u32(buffer, endianness);
+        self.p_type = match self.i_type {
+            PT_LOAD => ProgramHeaderType::Load,
+            PT_DYNAMIC => ProgramHeaderType::Dynamic,
+            PT_INTERP => ProgramHeaderType::Interp,
+            PT_NOTE => ProgramHeaderType::Note,
+            PT_PHDR => ProgramHeaderType::Phdr,
+            PT_GNU_STACK => ProgramHeaderType::GnuStack,
+            _ => ProgramHeaderType::Unknown,
+        };
+        // Elf64_Phdr has p_flags right after p_type; Elf32_Phdr has it after p_memsz
+        match class {
+            ElfClass::Elf64 => {
+                f.read_exact(&mut buffer)?;
+                self.flags = utils::bytes_to_u32(buffer, endianness);
+                self.offset = utils::read_addr(f, class, endianness)?;
+                self.vaddr = utils::read_addr(f, class, endianness)?;
+                self.paddr = utils::read_addr(f, class, endianness)?;
+                self.filesz = utils::read_addr(f, class, endianness)?;
+                self.memsz = utils::read_addr(f, class, endianness)?;
+                self.align = utils::read_addr(f, class, endianness)?;
+            }
+            ElfClass::Elf32 => {
+                self.offset = utils::read_addr(f, class, endianness)?;
+                self.vaddr = utils::read_addr(f, class, endianness)?;
+                self.paddr = utils::read_addr(f, class, endianness)?;
+                self.filesz = utils::read_addr(f, class, endianness)?;
+                self.memsz = utils::read_addr(f, class, endianness)?;
+                f.read_exact(&mut buffer)?;
+                self.flags = utils::bytes_to_u32(buffer, endianness);
+                self.align = utils::read_addr(f, class, endianness)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn type_to_string(&self) -> String {
+        match &self.p_type {
+            ProgramHeaderType::Load => "Loadable Segment",
+            ProgramHeaderType::Dynamic => "Dynamic Linking Table",
+            ProgramHeaderType::Interp => "Interpreter",
+            ProgramHeaderType::Note => "Notes",
+            ProgramHeaderType::Phdr => "Program Header Table",
+            ProgramHeaderType::GnuStack => "GNU Stack",
+            ProgramHeaderType::Unknown => "Unknown Type",
+        }.to_owned()
+    }
+
+    fn flags_to_string(&self) -> String {
+        let mut flags = ['-'; 5];
+        flags[0] = '[';
+        if self.flags & 0b100 == 0b100 {
+            flags[1] = 'R';
+        }
+        if self.flags & 0b010 == 0b010 {
+            flags[2] = 'W';
+        }
+        if self.flags & 0b001 == 0b001 {
+            flags[3] = 'E';
+        }
+        flags[4] = ']';
+        flags.iter().collect()
+    }
+}
+
+impl std::fmt::Display for ProgramHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "type: {:25} flags: {}
+vaddr: {:<22x} offset: {:<18x} filesz: {:<16x} memsz: {:x}",
+            self.type_to_string(),
+            self.flags_to_string(),
+            self.vaddr,
+            self.offset,
+            self.filesz,
+            self.memsz
+        )
+    }
+}
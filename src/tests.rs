@@ -1,8 +1,27 @@
 use std::fs::File;
+    use std::io::Write;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use super::writer::{SectionSpec, Writer};
+
+    // Parsing tests need a real dynamically-linked ELF binary on disk; allow pointing this at
+    // a specific file via ELFIN_TEST_BINARY for environments where /usr/bin/python doesn't exist
+    fn test_binary_path() -> String {
+        if let Ok(path) = std::env::var("ELFIN_TEST_BINARY") {
+            return path;
+        }
+        for candidate in &["/usr/bin/python", "/usr/bin/python3", "/bin/ls", "/usr/bin/ls"] {
+            if std::path::Path::new(candidate).exists() {
+                return candidate.to_string();
+            }
+        }
+        panic!("no suitable ELF test binary found; set ELFIN_TEST_BINARY to one")
+    }
+
     #[test]
     fn it_works() {
         let mut headers = super::ElfHeaders::new();
-        let mut file = File::open("/usr/bin/python").unwrap();
+        let mut file = File::open(test_binary_path()).unwrap();
         let result = headers.from_file(&mut file);
         match result {
             Ok(x) => assert_eq!(x, ()),
@@ -14,4 +33,313 @@ use std::fs::File;
         for section in sections {
             println!("{}", section);
         }
+    }
+
+    #[test]
+    fn round_trip_write_and_reparse() {
+        let mut headers = super::ElfHeaders::new();
+        let mut file = File::open(test_binary_path()).unwrap();
+        headers.from_file(&mut file).unwrap();
+        let sections = headers.sections_from_file(&mut file).unwrap();
+
+        // The null section (index 0) and .shstrtab are both synthesized fresh by the writer,
+        // so skip copying the originals in
+        let mut writer = Writer::new();
+        writer.machine = headers.machine;
+        for section in &sections {
+            if section.header.sec_type == super::SectionType::Unused
+                || section.header.str_name == ".shstrtab"
+            {
+                continue;
+            }
+            let mut spec = SectionSpec::new(&section.header.str_name, section.header.i_type);
+            spec.flags = section.header.flags;
+            spec.data = section.data.clone();
+            spec.link = section.header.link;
+            spec.info = section.header.info;
+            spec.entry_size = section.header.entry_size;
+            writer.add_section(spec);
+        }
+
+        let out_path = "/tmp/elfin_round_trip_test.o";
+        let mut out_file = File::create(out_path).unwrap();
+        writer.write_to_file(&mut out_file).unwrap();
+        drop(out_file);
+
+        let mut reread_headers = super::ElfHeaders::new();
+        let mut reread_file = File::open(out_path).unwrap();
+        reread_headers.from_file(&mut reread_file).unwrap();
+        let reread_sections = reread_headers.sections_from_file(&mut reread_file).unwrap();
+        std::fs::remove_file(out_path).ok();
+
+        let original_names: Vec<&str> = sections
+            .iter()
+            .filter(|s| s.header.sec_type != super::SectionType::Unused && s.header.str_name != ".shstrtab")
+            .map(|s| s.header.str_name.as_str())
+            .collect();
+        let reread_names: Vec<&str> = reread_sections
+            .iter()
+            .filter(|s| s.header.sec_type != super::SectionType::Unused && s.header.str_name != ".shstrtab")
+            .map(|s| s.header.str_name.as_str())
+            .collect();
+        assert_eq!(original_names, reread_names);
+    }
+
+    #[test]
+    fn parses_32bit_big_endian_header() {
+        // A minimal ELF32 big-endian e_ident + ehdr, built by hand since no 32-bit/BE fixture
+        // binary is available in this environment
+        let mut buf = vec![0x7f, b'E', b'L', b'F', 1, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        buf.extend_from_slice(&2u16.to_be_bytes()); // e_type: ET_EXEC
+        buf.extend_from_slice(&40u16.to_be_bytes()); // e_machine: EM_ARM
+        buf.extend_from_slice(&1u32.to_be_bytes()); // e_version
+        buf.extend_from_slice(&0x8048u32.to_be_bytes()); // e_entry
+        buf.extend_from_slice(&52u32.to_be_bytes()); // e_phoff
+        buf.extend_from_slice(&0u32.to_be_bytes()); // e_shoff
+        buf.extend_from_slice(&0u32.to_be_bytes()); // e_flags
+        buf.extend_from_slice(&52u16.to_be_bytes()); // e_ehsize
+        buf.extend_from_slice(&32u16.to_be_bytes()); // e_phentsize
+        buf.extend_from_slice(&1u16.to_be_bytes()); // e_phnum
+        buf.extend_from_slice(&0u16.to_be_bytes()); // e_shentsize
+        buf.extend_from_slice(&0u16.to_be_bytes()); // e_shnum
+        buf.extend_from_slice(&0u16.to_be_bytes()); // e_shstrndx
+        assert_eq!(buf.len(), 52);
+
+        let path = "/tmp/elfin_32bit_be_header_test";
+        std::fs::write(path, &buf).unwrap();
+        let mut file = File::open(path).unwrap();
+        let mut headers = super::ElfHeaders::new();
+        headers.from_file(&mut file).unwrap();
+        std::fs::remove_file(path).ok();
+
+        assert_eq!(headers.class, super::ElfClass::Elf32);
+        assert_eq!(headers.endianness, super::Endianness::Big);
+        assert_eq!(headers.machine, 40);
+        assert_eq!(headers.entry_addr, 0x8048);
+        assert_eq!(headers.program_offset, 52);
+        assert_eq!(headers.pheader_count, 1);
+    }
+
+    #[test]
+    fn programs_from_file_reads_32bit_field_order() {
+        // Elf32_Phdr orders p_flags last (after p_memsz), unlike Elf64_Phdr which has it
+        // right after p_type; this exercises that field-order split in ProgramHeader::from_file
+        let mut phdr = vec![];
+        phdr.extend_from_slice(&1u32.to_le_bytes()); // p_type: PT_LOAD
+        phdr.extend_from_slice(&0u32.to_le_bytes()); // p_offset
+        phdr.extend_from_slice(&0x8000u32.to_le_bytes()); // p_vaddr
+        phdr.extend_from_slice(&0x8000u32.to_le_bytes()); // p_paddr
+        phdr.extend_from_slice(&0x100u32.to_le_bytes()); // p_filesz
+        phdr.extend_from_slice(&0x200u32.to_le_bytes()); // p_memsz
+        phdr.extend_from_slice(&5u32.to_le_bytes()); // p_flags: PF_R | PF_X
+        phdr.extend_from_slice(&0x1000u32.to_le_bytes()); // p_align
+        assert_eq!(phdr.len(), 32);
+
+        let path = "/tmp/elfin_32bit_phdr_test";
+        std::fs::write(path, &phdr).unwrap();
+        let mut file = File::open(path).unwrap();
+
+        let mut headers = super::ElfHeaders::new();
+        headers.class = super::ElfClass::Elf32;
+        headers.endianness = super::Endianness::Little;
+        headers.program_offset = 0;
+        headers.pheader_size = 32;
+        headers.pheader_count = 1;
+
+        let programs = headers.programs_from_file(&mut file).unwrap();
+        std::fs::remove_file(path).ok();
+
+        assert_eq!(programs.len(), 1);
+        let program = &programs[0];
+        assert!(program.p_type == super::ProgramHeaderType::Load);
+        assert_eq!(program.vaddr, 0x8000);
+        assert_eq!(program.memsz, 0x200);
+        assert_eq!(program.flags, 5);
+    }
+
+    #[test]
+    fn decompressed_data_inflates_zlib_section() {
+        let original = b"hello elfin, this is the uncompressed payload".to_vec();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&original).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // Build a minimal Elf64_Chdr (ch_type, ch_reserved, ch_size, ch_addralign) followed
+        // by the compressed payload, matching what decompressed_data expects to find
+        let mut data = vec![0u8; 24];
+        data[0..4].copy_from_slice(&super::ELFCOMPRESS_ZLIB.to_le_bytes());
+        data[8..16].copy_from_slice(&(original.len() as u64).to_le_bytes());
+        data.extend_from_slice(&compressed);
+
+        let mut header = super::SectionHeader::new(0);
+        header.class = super::ElfClass::Elf64;
+        header.endianness = super::Endianness::Little;
+        header.flags = super::SHF_COMPRESSED;
+        let section = super::Section { header, data };
+
+        let decompressed = section.decompressed_data().unwrap();
+        assert_eq!(decompressed, original);
+    }
+
+    #[test]
+    fn decompressed_data_rejects_truncated_chdr() {
+        let mut header = super::SectionHeader::new(0);
+        header.class = super::ElfClass::Elf64;
+        header.endianness = super::Endianness::Little;
+        header.flags = super::SHF_COMPRESSED;
+        let section = super::Section {
+            header,
+            data: vec![0u8; 4],
+        };
+        assert!(section.decompressed_data().is_err());
+    }
+
+    #[test]
+    fn relocations_from_section_computes_et_rel_target_offset() {
+        let mut null_section = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![],
+        };
+        null_section.header.class = super::ElfClass::Elf64;
+        null_section.header.endianness = super::Endianness::Little;
+
+        let mut strtab = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![0, b'f', b'o', b'o', 0],
+        };
+        strtab.header.class = super::ElfClass::Elf64;
+        strtab.header.endianness = super::Endianness::Little;
+        strtab.header.sec_type = super::SectionType::StringTable;
+
+        let mut sym_entry = vec![0u8; 24];
+        sym_entry[0..4].copy_from_slice(&1u32.to_le_bytes()); // st_name -> "foo"
+        sym_entry[4] = 0x12; // binding Global, type Func
+        sym_entry[6..8].copy_from_slice(&1u16.to_le_bytes()); // st_shndx
+        sym_entry[8..16].copy_from_slice(&0x1000u64.to_le_bytes()); // st_value
+        sym_entry[16..24].copy_from_slice(&0x10u64.to_le_bytes()); // st_size
+        let mut symtab = super::Section {
+            header: super::SectionHeader::new(0),
+            data: sym_entry,
+        };
+        symtab.header.class = super::ElfClass::Elf64;
+        symtab.header.endianness = super::Endianness::Little;
+        symtab.header.sec_type = super::SectionType::SymbolTable;
+        symtab.header.link = 1; // points at strtab
+        symtab.header.entry_size = 24;
+
+        let mut target = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![],
+        };
+        target.header.class = super::ElfClass::Elf64;
+        target.header.endianness = super::Endianness::Little;
+        target.header.offset = 0x400;
+
+        let sections = vec![null_section, strtab, symtab, target];
+
+        let mut rela_entry = vec![0u8; 24];
+        rela_entry[0..8].copy_from_slice(&0x20u64.to_le_bytes()); // r_offset
+        rela_entry[8..16].copy_from_slice(&0x101u64.to_le_bytes()); // r_info: sym 0, type 0x101
+        rela_entry[16..24].copy_from_slice(&5i64.to_le_bytes()); // r_addend
+        let mut rela = super::Section {
+            header: super::SectionHeader::new(0),
+            data: rela_entry,
+        };
+        rela.header.class = super::ElfClass::Elf64;
+        rela.header.endianness = super::Endianness::Little;
+        rela.header.sec_type = super::SectionType::Rela;
+        rela.header.link = 2; // points at symtab
+        rela.header.info = 3; // points at target, only meaningful for ET_REL
+
+        let relocations = rela.relocations_from_section(&sections, super::TYPE_RELO).unwrap();
+        assert_eq!(relocations.len(), 1);
+        assert_eq!(relocations[0].offset, 0x20);
+        assert_eq!(relocations[0].addend, 5);
+        assert_eq!(relocations[0].target_offset, Some(0x420));
+
+        // For a non-relocatable file type, r_offset is already an absolute vaddr, so no
+        // target_offset should be computed (and sh_info need not even be a valid section index)
+        let relocations = rela.relocations_from_section(&sections, super::TYPE_EXEC).unwrap();
+        assert_eq!(relocations[0].target_offset, None);
+    }
+
+    #[test]
+    fn dynamic_from_section_resolves_needed_soname() {
+        let mut null_section = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![],
+        };
+        null_section.header.class = super::ElfClass::Elf64;
+        null_section.header.endianness = super::Endianness::Little;
+
+        let mut strtab = super::Section {
+            header: super::SectionHeader::new(0),
+            data: b"libfoo.so.1\0".to_vec(),
+        };
+        strtab.header.class = super::ElfClass::Elf64;
+        strtab.header.endianness = super::Endianness::Little;
+        strtab.header.sec_type = super::SectionType::StringTable;
+
+        let mut data = vec![0u8; 32];
+        data[0..8].copy_from_slice(&1i64.to_le_bytes()); // DT_NEEDED
+        data[8..16].copy_from_slice(&0u64.to_le_bytes()); // offset 0 in strtab
+        data[16..24].copy_from_slice(&0i64.to_le_bytes()); // DT_NULL
+        let mut dynamic = super::Section {
+            header: super::SectionHeader::new(0),
+            data,
+        };
+        dynamic.header.class = super::ElfClass::Elf64;
+        dynamic.header.endianness = super::Endianness::Little;
+        dynamic.header.sec_type = super::SectionType::Dynamic;
+        dynamic.header.link = 1;
+
+        let sections = vec![null_section, strtab];
+        let entries = dynamic.dynamic_from_section(&sections).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].kind == super::DynTag::Needed);
+        assert_eq!(entries[0].name.as_deref(), Some("libfoo.so.1"));
+        assert!(entries[1].kind == super::DynTag::Null);
+    }
+
+    #[test]
+    fn dynamic_from_section_stops_on_truncated_entry() {
+        let mut strtab = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![],
+        };
+        strtab.header.class = super::ElfClass::Elf64;
+        strtab.header.endianness = super::Endianness::Little;
+        strtab.header.sec_type = super::SectionType::StringTable;
+
+        let mut dynamic = super::Section {
+            header: super::SectionHeader::new(0),
+            data: vec![0u8; 10], // shorter than one 16-byte Elf64 entry
+        };
+        dynamic.header.class = super::ElfClass::Elf64;
+        dynamic.header.endianness = super::Endianness::Little;
+        dynamic.header.sec_type = super::SectionType::Dynamic;
+        dynamic.header.link = 0;
+
+        let sections = vec![strtab];
+        let entries = dynamic.dynamic_from_section(&sections).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn dynsym_symbols_from_dynamically_linked_binary() {
+        let mut headers = super::ElfHeaders::new();
+        let mut file = File::open(test_binary_path()).unwrap();
+        headers.from_file(&mut file).unwrap();
+        let sections = headers.sections_from_file(&mut file).unwrap();
+
+        // Most dynamically linked binaries are stripped of .symtab but always keep .dynsym
+        let dynsym = sections
+            .iter()
+            .find(|s| s.header.str_name == ".dynsym")
+            .expect(".dynsym section not found");
+        assert!(dynsym.header.sec_type == super::SectionType::SymbolTable);
+
+        let symbols = dynsym.symbols_from_section(&sections).unwrap();
+        assert!(!symbols.is_empty());
     }
\ No newline at end of file
@@ -1,21 +1,68 @@
-// These are hard-coded to little-endian, now
-pub fn bytes_to_u16(bytes: [u8; 2]) -> u16 {
-    bytes[0] as u16 | (bytes[1] as u16) << 8
+use std::fs::File;
+use std::io::prelude::*;
+
+use super::{ElfClass, ElfError, Endianness};
+
+// Endianness-aware byte conversions; the file's e_ident[EI_DATA] byte picks the branch
+pub fn bytes_to_u16(bytes: [u8; 2], endianness: Endianness) -> u16 {
+    match endianness {
+        Endianness::Little => bytes[0] as u16 | (bytes[1] as u16) << 8,
+        Endianness::Big => (bytes[0] as u16) << 8 | bytes[1] as u16,
+    }
 }
 
-pub fn bytes_to_u32(bytes: [u8; 4]) -> u32 {
-    bytes[0] as u32 | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16 | (bytes[3] as u32) << 24
+pub fn bytes_to_u32(bytes: [u8; 4], endianness: Endianness) -> u32 {
+    match endianness {
+        Endianness::Little => {
+            bytes[0] as u32 | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16 | (bytes[3] as u32) << 24
+        }
+        Endianness::Big => {
+            (bytes[0] as u32) << 24 | (bytes[1] as u32) << 16 | (bytes[2] as u32) << 8 | bytes[3] as u32
+        }
+    }
 }
 
-pub fn bytes_to_u64(bytes: [u8; 8]) -> u64 {
+pub fn bytes_to_u64(bytes: [u8; 8], endianness: Endianness) -> u64 {
     let mut num = 0;
     for (i, each) in bytes.into_iter().enumerate() {
-        num = num | (*each as u64) << (i as u64) * 8;
+        let shift = match endianness {
+            Endianness::Little => i as u64,
+            Endianness::Big => 7 - i as u64,
+        };
+        num = num | (*each as u64) << shift * 8;
     }
     num
 }
 
+// Convenience wrappers for decoding fixed-width fields out of a variable-length buffer,
+// e.g. one entry of a symbol/relocation table
+pub fn slice_to_u16(bytes: &[u8], endianness: Endianness) -> u16 {
+    bytes_to_u16([bytes[0], bytes[1]], endianness)
+}
+
+pub fn slice_to_u32(bytes: &[u8], endianness: Endianness) -> u32 {
+    bytes_to_u32([bytes[0], bytes[1], bytes[2], bytes[3]], endianness)
+}
+
+pub fn slice_to_u64(bytes: &[u8], endianness: Endianness) -> u64 {
+    bytes_to_u64(
+        [
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ],
+        endianness,
+    )
+}
+
+pub fn slice_to_i64(bytes: &[u8], endianness: Endianness) -> i64 {
+    slice_to_u64(bytes, endianness) as i64
+}
+
+// Callers now feed this file-controlled offsets (st_name, DT_NEEDED/DT_SONAME) in addition to
+// section names, so an out-of-range start must not panic; just report no string found
 pub fn read_null_term_str(start: u32, bytes: &Vec<u8>) -> String {
+    if start as usize >= bytes.len() {
+        return String::new();
+    }
     let mut s = String::new();
     for &byte in &bytes[start as usize..] {
         if byte == 0 {
@@ -25,3 +72,20 @@ pub fn read_null_term_str(start: u32, bytes: &Vec<u8>) -> String {
     }
     s
 }
+
+// Reads a field that is 4 bytes wide under ELFCLASS32 and 8 bytes wide under ELFCLASS64,
+// widening the 32-bit case to a u64 so callers get a single type regardless of class
+pub fn read_addr(f: &mut File, class: ElfClass, endianness: Endianness) -> Result<u64, ElfError> {
+    match class {
+        ElfClass::Elf32 => {
+            let mut buffer = [0; 4];
+            f.read_exact(&mut buffer)?;
+            Ok(bytes_to_u32(buffer, endianness) as u64)
+        }
+        ElfClass::Elf64 => {
+            let mut buffer = [0; 8];
+            f.read_exact(&mut buffer)?;
+            Ok(bytes_to_u64(buffer, endianness))
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// A companion to the read path: serializes an accumulated set of sections back into a valid
+// little-endian ELF relocatable object file, mirroring the write support the `object` crate
+// exposes.
+use std::fs::File;
+use std::io::prelude::*;
+
+use crate::ElfError;
+
+const EHDR_SIZE: u16 = 64;
+const SHDR_SIZE: u16 = 64;
+const EV_CURRENT: u32 = 1;
+
+fn push_u16(out: &mut Vec<u8>, v: u16) {
+    out.push((v & 0xff) as u8);
+    out.push((v >> 8) as u8);
+}
+
+fn push_u32(out: &mut Vec<u8>, v: u32) {
+    for i in 0..4 {
+        out.push((v >> (i * 8)) as u8);
+    }
+}
+
+fn push_u64(out: &mut Vec<u8>, v: u64) {
+    for i in 0..8 {
+        out.push((v >> (i * 8)) as u8);
+    }
+}
+
+// A section to be emitted; unlike the read-path `Section`/`SectionHeader`, this carries only
+// what a writer needs to supply, leaving offsets and the name-table index to be computed.
+// `sh_type` is the raw SHT_* value rather than the read path's `SectionType`, since that
+// enum only covers the types this crate interprets and would lose anything it doesn't.
+pub struct SectionSpec {
+    pub name: String,
+    pub sh_type: u32,
+    pub flags: u64,
+    pub data: Vec<u8>,
+    pub link: u32,
+    pub info: u32,
+    pub align: u64,
+    pub entry_size: u64,
+}
+
+impl SectionSpec {
+    pub fn new(name: &str, sh_type: u32) -> Self {
+        SectionSpec {
+            name: name.to_owned(),
+            sh_type,
+            flags: 0,
+            data: vec![],
+            link: 0,
+            info: 0,
+            align: 0,
+            entry_size: 0,
+        }
+    }
+}
+
+// Accumulates sections and emits them as a 64-bit little-endian relocatable object file
+pub struct Writer {
+    pub machine: u16,
+    pub cpu_flags: u32,
+    sections: Vec<SectionSpec>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Writer {
+            machine: 0,
+            cpu_flags: 0,
+            sections: vec![],
+        }
+    }
+
+    pub fn add_section(&mut self, section: SectionSpec) {
+        self.sections.push(section);
+    }
+
+    pub fn write_to_file(&self, f: &mut File) -> Result<(), ElfError> {
+        // Section 0 is always a reserved null entry; .shstrtab is synthesized and appended last
+        let mut shstrtab_data = vec![0u8];
+        let mut name_offsets = Vec::with_capacity(self.sections.len());
+        for section in &self.sections {
+            name_offsets.push(shstrtab_data.len() as u32);
+            shstrtab_data.extend_from_slice(section.name.as_bytes());
+            shstrtab_data.push(0);
+        }
+        let shstrtab_name_offset = shstrtab_data.len() as u32;
+        shstrtab_data.extend_from_slice(b".shstrtab");
+        shstrtab_data.push(0);
+
+        // Lay out section data immediately after the ELF header, then the freshly built
+        // .shstrtab, then the section header table
+        let mut offset = EHDR_SIZE as u64;
+        let mut data_offsets = Vec::with_capacity(self.sections.len());
+        for section in &self.sections {
+            data_offsets.push(offset);
+            offset += section.data.len() as u64;
+        }
+        let shstrtab_offset = offset;
+        offset += shstrtab_data.len() as u64;
+        let section_offset = offset;
+
+        let sheader_count = self.sections.len() as u16 + 2; // null entry + sections + .shstrtab
+        let str_header_index = sheader_count - 1;
+
+        let mut ehdr = Vec::with_capacity(EHDR_SIZE as usize);
+        ehdr.extend_from_slice(&[0x7f, b'E', b'L', b'F']);
+        ehdr.push(crate::ELFCLASS64);
+        ehdr.push(crate::ELFDATA2LSB);
+        ehdr.push(1); // EI_VERSION
+        ehdr.extend_from_slice(&[0; 9]); // EI_OSABI, EI_ABIVERSION, padding
+        push_u16(&mut ehdr, crate::TYPE_RELO);
+        push_u16(&mut ehdr, self.machine);
+        push_u32(&mut ehdr, EV_CURRENT);
+        push_u64(&mut ehdr, 0); // entry_addr: none for a relocatable object
+        push_u64(&mut ehdr, 0); // program_offset: no program headers
+        push_u64(&mut ehdr, section_offset);
+        push_u32(&mut ehdr, self.cpu_flags);
+        push_u16(&mut ehdr, EHDR_SIZE);
+        push_u16(&mut ehdr, 0); // pheader_size
+        push_u16(&mut ehdr, 0); // pheader_count
+        push_u16(&mut ehdr, SHDR_SIZE);
+        push_u16(&mut ehdr, sheader_count);
+        push_u16(&mut ehdr, str_header_index);
+        f.write_all(&ehdr)?;
+
+        for section in &self.sections {
+            f.write_all(&section.data)?;
+        }
+        f.write_all(&shstrtab_data)?;
+
+        let mut shdrs = Vec::with_capacity(SHDR_SIZE as usize * sheader_count as usize);
+        push_shdr(&mut shdrs, 0, 0, 0, 0, 0, 0, 0, 0, 0);
+        for (i, section) in self.sections.iter().enumerate() {
+            push_shdr(
+                &mut shdrs,
+                name_offsets[i],
+                section.sh_type,
+                section.flags,
+                data_offsets[i],
+                section.data.len() as u64,
+                section.link,
+                section.info,
+                section.align,
+                section.entry_size,
+            );
+        }
+        push_shdr(
+            &mut shdrs,
+            shstrtab_name_offset,
+            crate::SHT_STRTAB,
+            0,
+            shstrtab_offset,
+            shstrtab_data.len() as u64,
+            0,
+            0,
+            1,
+            0,
+        );
+        f.write_all(&shdrs)?;
+
+        Ok(())
+    }
+}
+
+fn push_shdr(
+    out: &mut Vec<u8>,
+    name: u32,
+    sh_type: u32,
+    flags: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    align: u64,
+    entry_size: u64,
+) {
+    push_u32(out, name);
+    push_u32(out, sh_type);
+    push_u64(out, flags);
+    push_u64(out, 0); // sh_addr: unlinked, no virtual address yet
+    push_u64(out, offset);
+    push_u64(out, size);
+    push_u32(out, link);
+    push_u32(out, info);
+    push_u64(out, align);
+    push_u64(out, entry_size);
+}